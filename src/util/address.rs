@@ -63,6 +63,41 @@ impl Address {
         }
     }
 
+    /// Parses a scriptPubKey back into the address that it pays, recognizing the standard
+    /// pay-to-pubkey-hash and pay-to-script-hash templates. Returns `None` for any other
+    /// (non-standard or malformed) script.
+    pub fn from_script(script: &script::Script, network: Network) -> Option<Address> {
+        let bytes = &script[..];
+
+        if bytes.len() == 25
+            && bytes[0] == opcodes::All::OP_DUP.into_u8()
+            && bytes[1] == opcodes::All::OP_HASH160.into_u8()
+            && bytes[2] == 0x14
+            && bytes[23] == opcodes::All::OP_EQUALVERIFY.into_u8()
+            && bytes[24] == opcodes::All::OP_CHECKSIG.into_u8()
+        {
+            return Some(Address {
+                ty: Type::PubkeyHash,
+                network: network,
+                hash: Hash160::from(&bytes[3..23])
+            });
+        }
+
+        if bytes.len() == 23
+            && bytes[0] == opcodes::All::OP_HASH160.into_u8()
+            && bytes[1] == 0x14
+            && bytes[22] == opcodes::All::OP_EQUAL.into_u8()
+        {
+            return Some(Address {
+                ty: Type::ScriptHash,
+                network: network,
+                hash: Hash160::from(&bytes[2..22])
+            });
+        }
+
+        None
+    }
+
     /// Generates a script pubkey spending to this address
     #[inline]
     pub fn script_pubkey(&self) -> script::Script {
@@ -253,6 +288,41 @@ mod tests {
         assert_eq!(FromBase58::from_base58check("33iFwdLuRpW1uK1RTRqsoi8rR4NpDzk66k"), Ok(addr));
     }
 
+    #[test]
+    fn test_p2pkh_from_script() {
+        let addr = Address {
+            ty: Type::PubkeyHash,
+            network: Bitcoin,
+            hash: Hash160::from(&"162c5ea71c0b23f5b9022ef047c4a86470a5b070".from_hex().unwrap()[..])
+        };
+
+        let script = hex_script!("76a914162c5ea71c0b23f5b9022ef047c4a86470a5b07088ac");
+        assert_eq!(Address::from_script(&script, Bitcoin), Some(addr));
+    }
+
+    #[test]
+    fn test_p2sh_from_script() {
+        let addr = Address {
+            ty: Type::ScriptHash,
+            network: Bitcoin,
+            hash: Hash160::from(&"162c5ea71c0b23f5b9022ef047c4a86470a5b070".from_hex().unwrap()[..])
+        };
+
+        let script = hex_script!("a914162c5ea71c0b23f5b9022ef047c4a86470a5b07087");
+        assert_eq!(Address::from_script(&script, Bitcoin), Some(addr));
+    }
+
+    #[test]
+    fn test_from_script_rejects_non_standard_scripts() {
+        // A bare 20-byte push with no surrounding opcodes matches neither template.
+        let script = hex_script!("14162c5ea71c0b23f5b9022ef047c4a86470a5b070");
+        assert_eq!(Address::from_script(&script, Bitcoin), None);
+
+        // OP_RETURN data carrier.
+        let script = hex_script!("6a0b68656c6c6f20776f726c64");
+        assert_eq!(Address::from_script(&script, Bitcoin), None);
+    }
+
     #[test]
     fn test_key_derivation() {
         // testnet compressed
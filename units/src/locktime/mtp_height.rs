@@ -2,6 +2,10 @@
 
 //! Provides [`MtpAndHeight`] structure for the `rust-bitcoin` `relative::LockTime` type.
 
+use core::fmt;
+
+use super::absolute;
+use super::relative;
 use super::relative::{Height, Time, TimeOverflowError};
 use crate::BlockTime;
 
@@ -33,6 +37,37 @@ impl MtpAndHeight {
         MtpAndHeight { mtp, height }
     }
 
+    /// Build from a tip height and between 1 and 11 recent block timestamps.
+    ///
+    /// Unlike [`new`](Self::new), this does not require a full 11-block window, so it can
+    /// represent the early chain (heights 0-10), where fewer than 11 ancestors exist but MTP
+    /// is still defined. As in Bitcoin Core, the median is taken over however many timestamps
+    /// are actually available.
+    ///
+    /// # Parameters
+    /// * `height` - The height of the chain tip
+    /// * `timestamps` - 1 to 11 recent timestamps of block headers, in any order
+    ///
+    /// # Errors
+    /// Returns a [`FromTimestampsError`] if `timestamps` is empty or holds more than 11
+    /// entries.
+    pub fn from_timestamps(height: Height, timestamps: &[BlockTime]) -> Result<Self, FromTimestampsError> {
+        let len = timestamps.len();
+        if len == 0 {
+            return Err(FromTimestampsError::Empty);
+        }
+        if len > 11 {
+            return Err(FromTimestampsError::TooMany { got: len });
+        }
+
+        let mut buf = [BlockTime::from_u32(0); 11];
+        buf[..len].copy_from_slice(timestamps);
+        buf[..len].sort_unstable();
+        let mtp = buf[len / 2];
+
+        Ok(MtpAndHeight { mtp, height })
+    }
+
     /// Convert the MTP seconds to a Time value for comparison with relative timelocks
     ///
     /// # Errors
@@ -41,8 +76,110 @@ impl MtpAndHeight {
     pub fn mtp_as_time(self) -> Result<Time, TimeOverflowError> {
         Time::from_seconds_floor(self.mtp.to_u32())
     }
+
+    /// Checks whether a relative locktime is satisfied, given `self` as the spending tip's
+    /// state and `utxo_state` as the state when the spent output was confirmed.
+    ///
+    /// Implements BIP-68: a height-based lock is satisfied once at least `lock` blocks have
+    /// been mined since `utxo_state`, and a time-based lock is satisfied once at least `lock`
+    /// 512-second intervals have elapsed between the two MTPs. A height lock is never
+    /// satisfied by elapsed time, and vice versa. Returns `false`, rather than panicking or
+    /// wrapping, if `self` is behind `utxo_state` (i.e. spending was attempted before the
+    /// UTXO's confirmation height/MTP).
+    pub fn is_relative_lock_satisfied(self, utxo_state: MtpAndHeight, lock: relative::LockTime) -> bool {
+        match lock {
+            relative::LockTime::Blocks(needed) => {
+                match self.height.to_consensus_u32().checked_sub(utxo_state.height.to_consensus_u32()) {
+                    Some(elapsed) => elapsed >= needed.to_consensus_u32(),
+                    None => false,
+                }
+            }
+            relative::LockTime::Time(needed) => {
+                match self.mtp.to_u32().checked_sub(utxo_state.mtp.to_u32()) {
+                    Some(elapsed_secs) => (elapsed_secs / 512) >= needed.to_consensus_u32(),
+                    None => false,
+                }
+            }
+        }
+    }
+
+    /// Returns how many blocks or 512-second intervals are still needed before a relative
+    /// locktime would be satisfied, given `self` as the spending tip's state and
+    /// `utxo_state` as the state when the spent output was confirmed.
+    ///
+    /// Returns zero when the lock is already satisfied.
+    pub fn relative_lock_remaining(self, utxo_state: MtpAndHeight, lock: relative::LockTime) -> RelativeLockRemaining {
+        match lock {
+            relative::LockTime::Blocks(needed) => {
+                let elapsed = self.height.to_consensus_u32()
+                    .checked_sub(utxo_state.height.to_consensus_u32())
+                    .unwrap_or(0);
+                RelativeLockRemaining::Blocks(needed.to_consensus_u32().saturating_sub(elapsed))
+            }
+            relative::LockTime::Time(needed) => {
+                let elapsed = self.mtp.to_u32()
+                    .checked_sub(utxo_state.mtp.to_u32())
+                    .map(|secs| secs / 512)
+                    .unwrap_or(0);
+                RelativeLockRemaining::Intervals(needed.to_consensus_u32().saturating_sub(elapsed))
+            }
+        }
+    }
+
+    /// Checks whether an absolute locktime (nLockTime) is final relative to this tip.
+    ///
+    /// Implements BIP-113: a block-height lock is final once `self.height` has reached it, and
+    /// a time-based lock is final only once `self.mtp` (median-time-past), rather than the
+    /// tip's own timestamp, has moved strictly past it — matching Bitcoin Core's `IsFinalTx`,
+    /// which requires `nLockTime < nBlockTime` for time-based locks. Answers whether a
+    /// transaction carrying `lock` may be included in the block built on top of this tip.
+    pub fn is_absolute_lock_final(self, lock: absolute::LockTime) -> bool {
+        match lock {
+            absolute::LockTime::Blocks(height) => self.height.to_consensus_u32() >= height.to_consensus_u32(),
+            absolute::LockTime::Time(time) => self.mtp.to_u32() > time.to_consensus_u32(),
+        }
+    }
 }
 
+/// The amount of chain progress still needed before a relative locktime matures.
+///
+/// Returned by [`MtpAndHeight::relative_lock_remaining`]; the variant matches the kind of the
+/// `relative::LockTime` that was checked.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RelativeLockRemaining {
+    /// Blocks still needed before a height-based lock matures.
+    Blocks(u32),
+    /// 512-second intervals still needed before a time-based lock matures.
+    Intervals(u32),
+}
+
+/// Error returned by [`MtpAndHeight::from_timestamps`] when given an out-of-range number of
+/// timestamps.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FromTimestampsError {
+    /// `timestamps` was empty; at least one is required to compute a median time past.
+    Empty,
+    /// `timestamps` held more than the 11 entries BIP-68 MTP is defined over.
+    TooMany {
+        /// The number of timestamps that were passed in.
+        got: usize,
+    },
+}
+
+impl fmt::Display for FromTimestampsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            FromTimestampsError::Empty =>
+                f.write_str("at least one timestamp is required to compute a median time past"),
+            FromTimestampsError::TooMany { got } =>
+                write!(f, "at most 11 timestamps are allowed to compute a median time past, got {}", got),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FromTimestampsError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,4 +269,128 @@ mod tests {
         assert_eq!(chain_state.height, current_height);
         assert_eq!(utxo_state.height, utxo_height);
     }
+
+    #[test]
+    fn relative_height_lock_satisfied_and_remaining() {
+        let utxo_height = Height::from_height(100);
+        let utxo_state = MtpAndHeight::new(utxo_height, generate_timestamps(1_600_000_000, 600));
+
+        let lock = relative::LockTime::from_height(10);
+
+        let not_yet = MtpAndHeight::new(Height::from_height(105), generate_timestamps(1_600_003_000, 600));
+        assert!(!not_yet.is_relative_lock_satisfied(utxo_state, lock));
+        assert_eq!(not_yet.relative_lock_remaining(utxo_state, lock), RelativeLockRemaining::Blocks(5));
+
+        let exactly = MtpAndHeight::new(Height::from_height(110), generate_timestamps(1_600_006_000, 600));
+        assert!(exactly.is_relative_lock_satisfied(utxo_state, lock));
+        assert_eq!(exactly.relative_lock_remaining(utxo_state, lock), RelativeLockRemaining::Blocks(0));
+    }
+
+    #[test]
+    fn relative_time_lock_satisfied_and_remaining() {
+        let utxo_height = Height::from_height(100);
+        let utxo_timestamps = generate_timestamps(1_600_000_000, 600);
+        let utxo_state = MtpAndHeight::new(utxo_height, utxo_timestamps);
+
+        let lock = relative::LockTime::from_512_second_intervals(2);
+
+        let not_yet = MtpAndHeight::new(Height::from_height(101), generate_timestamps(1_600_000_600, 600));
+        assert!(!not_yet.is_relative_lock_satisfied(utxo_state, lock));
+        assert_eq!(not_yet.relative_lock_remaining(utxo_state, lock), RelativeLockRemaining::Intervals(1));
+
+        let enough = MtpAndHeight::new(Height::from_height(102), generate_timestamps(1_600_001_200, 600));
+        assert!(enough.is_relative_lock_satisfied(utxo_state, lock));
+        assert_eq!(enough.relative_lock_remaining(utxo_state, lock), RelativeLockRemaining::Intervals(0));
+    }
+
+    #[test]
+    fn relative_lock_never_satisfied_before_utxo_confirmation() {
+        let utxo_state = MtpAndHeight::new(Height::from_height(500), generate_timestamps(1_700_000_000, 600));
+        let spending_tip = MtpAndHeight::new(Height::from_height(400), generate_timestamps(1_699_000_000, 600));
+
+        assert!(!spending_tip.is_relative_lock_satisfied(utxo_state, relative::LockTime::from_height(1)));
+        assert!(!spending_tip.is_relative_lock_satisfied(
+            utxo_state,
+            relative::LockTime::from_512_second_intervals(1)
+        ));
+    }
+
+    #[test]
+    fn relative_lock_kinds_are_not_interchangeable() {
+        let utxo_state = MtpAndHeight::new(Height::from_height(100), generate_timestamps(1_600_000_000, 600));
+        // Plenty of elapsed time, but zero elapsed blocks.
+        let spending_tip = MtpAndHeight::new(Height::from_height(100), generate_timestamps(1_600_100_000, 600));
+
+        assert!(!spending_tip.is_relative_lock_satisfied(utxo_state, relative::LockTime::from_height(1)));
+    }
+
+    #[test]
+    fn absolute_height_lock_final() {
+        let tip = MtpAndHeight::new(Height::from_height(700_000), generate_timestamps(1_600_000_000, 600));
+
+        assert!(tip.is_absolute_lock_final(absolute::LockTime::from_height(700_000).unwrap()));
+        assert!(tip.is_absolute_lock_final(absolute::LockTime::from_height(699_999).unwrap()));
+        assert!(!tip.is_absolute_lock_final(absolute::LockTime::from_height(700_001).unwrap()));
+    }
+
+    #[test]
+    fn absolute_time_lock_final_uses_mtp_not_tip_timestamp() {
+        // MTP of this tip is 3000 seconds below the latest individual timestamp.
+        let tip = MtpAndHeight::new(Height::from_height(700_000), generate_timestamps(1_600_000_000, 600));
+        let mtp = tip.mtp.to_u32();
+
+        // BIP-113/IsFinalTx requires the MTP to move strictly past the locktime.
+        assert!(!tip.is_absolute_lock_final(absolute::LockTime::from_time(mtp).unwrap()));
+        assert!(tip.is_absolute_lock_final(absolute::LockTime::from_time(mtp - 1).unwrap()));
+        assert!(!tip.is_absolute_lock_final(absolute::LockTime::from_time(mtp + 1).unwrap()));
+        // BIP-113: the tip's own (later) timestamp must not be used for finality.
+        assert!(!tip.is_absolute_lock_final(absolute::LockTime::from_time(1_600_000_000).unwrap()));
+    }
+
+    #[test]
+    fn from_timestamps_handles_early_chain_heights() {
+        let height = Height::from_height(5);
+
+        // Genesis: a single timestamp is its own median.
+        let genesis = MtpAndHeight::from_timestamps(height, &[BlockTime::from_u32(1_231_006_505)]).unwrap();
+        assert_eq!(genesis.mtp, BlockTime::from_u32(1_231_006_505));
+
+        // Height 5: only 5 ancestors exist, median is the middle (index 2) after sorting.
+        let timestamps = [
+            BlockTime::from_u32(1_231_006_505),
+            BlockTime::from_u32(1_231_469_665),
+            BlockTime::from_u32(1_231_470_173),
+            BlockTime::from_u32(1_231_470_988),
+            BlockTime::from_u32(1_231_471_428),
+        ];
+        let early = MtpAndHeight::from_timestamps(height, &timestamps).unwrap();
+        assert_eq!(early.mtp, BlockTime::from_u32(1_231_470_173));
+    }
+
+    #[test]
+    fn from_timestamps_matches_new_for_a_full_window() {
+        let height = Height::from_height(500);
+        let timestamps = generate_timestamps(15_650_344, 600);
+
+        let via_new = MtpAndHeight::new(height, timestamps);
+        let via_from_timestamps = MtpAndHeight::from_timestamps(height, &timestamps).unwrap();
+
+        assert_eq!(via_new, via_from_timestamps);
+    }
+
+    #[test]
+    fn from_timestamps_rejects_empty_slice() {
+        let height = Height::from_height(0);
+        assert_eq!(MtpAndHeight::from_timestamps(height, &[]), Err(FromTimestampsError::Empty));
+    }
+
+    #[test]
+    fn from_timestamps_rejects_more_than_eleven() {
+        let height = Height::from_height(0);
+        let timestamps = [BlockTime::from_u32(0); 12];
+        assert_eq!(
+            MtpAndHeight::from_timestamps(height, &timestamps),
+            Err(FromTimestampsError::TooMany { got: 12 })
+        );
+    }
 }